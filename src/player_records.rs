@@ -1,10 +1,11 @@
 use std::{
     collections::HashMap,
     fmt::Display,
-    io::ErrorKind,
+    io::{ErrorKind, Write},
     ops::{Deref, DerefMut},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
+    time::SystemTime,
 };
 
 use anyhow::Context;
@@ -20,6 +21,10 @@ use crate::{
 
 // PlayerList
 
+/// How many rotated backups of the playerlist to keep on disk (`.1` is the
+/// most recent, `.MAX_BACKUPS` the oldest).
+const MAX_BACKUPS: usize = 3;
+
 #[derive(Serialize, Deserialize)]
 pub struct PlayerRecords {
     #[serde(skip)]
@@ -28,22 +33,41 @@ pub struct PlayerRecords {
 }
 
 impl PlayerRecords {
+    /// As [`PlayerRecords::load_or_create`], pulling `default_format` and
+    /// `active_profile` from `settings`.
+    pub fn load_or_create_from_settings(args: &Args, settings: &Settings) -> Self {
+        Self::load_or_create(
+            args,
+            settings.playerlist_format(),
+            settings.active_playerlist_profile(),
+        )
+    }
+
     /// Attempts to load the playerlist from the overriden (if provided in
     /// [Args]) or default location. If it cannot be found, then a new one
-    /// is created at the location.
+    /// is created at the location, using `default_format` to decide the
+    /// serializer and `active_profile` (via [`PlayerListProfiles`]) to pick
+    /// which file, unless `args` overrides the path outright.
     ///
     /// # Panics
     /// If the playerlist file was provided but could not be parsed, or another
     /// unexpected error occurred, to prevent data loss.
     #[allow(clippy::cognitive_complexity)]
-    pub fn load_or_create(args: &Args) -> Self {
+    pub fn load_or_create(
+        args: &Args,
+        default_format: PlayerListFormat,
+        active_profile: &str,
+    ) -> Self {
         // Playerlist
         let playerlist_path: PathBuf = args
         .playerlist
         .as_ref()
-        .map_or_else(Self::locate_playerlist_file, |i| Ok(i.into())).map_err(|e| {
-            tracing::error!("Could not find a suitable location for the playerlist: {} \nPlease specify a file path manually with --playerlist otherwise information may not be saved.", e); 
-        }).unwrap_or_else(|()| PathBuf::from("playerlist.json"));
+        .map_or_else(
+            || PlayerListProfiles::new(default_format).map(|profiles| profiles.path_for(active_profile)),
+            |i| Ok(i.into()),
+        ).map_err(|e| {
+            tracing::error!("Could not find a suitable location for the playerlist: {} \nPlease specify a file path manually with --playerlist otherwise information may not be saved.", e);
+        }).unwrap_or_else(|()| PathBuf::from("playerlist").with_extension(default_format.extension()));
 
         match Self::load_from(playerlist_path) {
             Ok(playerlist) => playerlist,
@@ -56,8 +80,10 @@ impl PlayerRecords {
             }
             Err(ConfigFilesError::IO(path, e)) if e.kind() == ErrorKind::NotFound => {
                 tracing::warn!("Could not locate {}, creating new playerlist.", &path);
+                let mut path: PathBuf = path.into();
+                path.set_extension(default_format.extension());
                 let mut playerlist = Self::default();
-                playerlist.set_path(path.into());
+                playerlist.set_path(path);
                 playerlist
             }
             Err(e) => {
@@ -70,16 +96,53 @@ impl PlayerRecords {
         }
     }
 
-    /// Attempt to load the `PlayerRecords` from the provided file
+    /// Attempt to load the `PlayerRecords` from the provided file. The format
+    /// is detected from the file's extension (`.ron` vs anything else,
+    /// treated as JSON). Falls back through the backup ring written by
+    /// [`PlayerRecords::save`] (most recent first) if `path` itself fails to
+    /// parse.
     ///
     /// # Errors
-    /// If the file could not be located, read, or parsed.
+    /// If the file could not be located or read, or if neither it nor any of
+    /// its backups could be parsed.
     pub fn load_from(path: PathBuf) -> Result<Self, ConfigFilesError> {
-        let contents = std::fs::read_to_string(&path)
+        match Self::read_and_parse(&path) {
+            Ok(mut playerlist) => {
+                playerlist.path = path;
+                Ok(playerlist)
+            }
+            Err(e @ ConfigFilesError::IO(_, _)) => Err(e),
+            Err(e) => {
+                for n in 1..=MAX_BACKUPS {
+                    let backup = Self::backup_path(&path, n);
+                    if let Ok(mut playerlist) = Self::read_and_parse(&backup) {
+                        tracing::warn!(
+                            "{} could not be parsed, recovered playerlist from backup {}",
+                            path.display(),
+                            backup.display()
+                        );
+                        playerlist.path = path;
+                        return Ok(playerlist);
+                    }
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Read and deserialize a playerlist file without touching `self.path` or
+    /// falling back to backups.
+    fn read_and_parse(path: &Path) -> Result<Self, ConfigFilesError> {
+        let contents = std::fs::read_to_string(path)
             .map_err(|e| ConfigFilesError::IO(path.to_string_lossy().into(), e))?;
-        let mut playerlist: Self = serde_json::from_str(&contents)
-            .map_err(|e| ConfigFilesError::Json(path.to_string_lossy().into(), e))?;
-        playerlist.path = path;
+
+        let mut playerlist: Self = match PlayerListFormat::from_path(path) {
+            PlayerListFormat::Json => serde_json::from_str(&contents)
+                .map_err(|e| ConfigFilesError::Json(path.to_string_lossy().into(), e))?,
+            PlayerListFormat::Ron => ron::from_str(&contents).with_context(|| {
+                format!("Failed to parse RON playerlist at {}", path.display())
+            })?,
+        };
 
         // Map all of the steamids to the records. They were not included when
         // serializing/deserializing the records to prevent duplication in the
@@ -97,17 +160,204 @@ impl PlayerRecords {
         Ok(playerlist)
     }
 
-    /// Attempt to save the `PlayerRecords` to the file it was loaded from
+    /// Attempt to save the `PlayerRecords` to the file it was loaded from, in
+    /// whichever format that file's extension implies. Writes to a sibling
+    /// `.tmp` file, `fsync`s, then renames over the real path so a crash
+    /// mid-write never leaves a truncated file, rotating the previous file
+    /// into the backup ring first.
     ///
     /// # Errors
     /// If it failed to serialize or write back to the file.
     pub fn save(&self) -> Result<(), ConfigFilesError> {
-        let contents = serde_json::to_string(self).context("Failed to serialize playerlist.")?;
-        std::fs::write(&self.path, contents)
+        let contents = match PlayerListFormat::from_path(&self.path) {
+            PlayerListFormat::Json => {
+                serde_json::to_string(self).context("Failed to serialize playerlist.")?
+            }
+            PlayerListFormat::Ron => {
+                ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+                    .context("Failed to serialize playerlist.")?
+            }
+        };
+
+        let tmp_path = Self::sibling_with_suffix(&self.path, "tmp");
+        let write_tmp = || -> std::io::Result<()> {
+            let mut file = std::fs::File::create(&tmp_path)?;
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()
+        };
+        write_tmp().map_err(|e| ConfigFilesError::IO(tmp_path.to_string_lossy().into(), e))?;
+
+        if self.path.exists() {
+            Self::rotate_backups(&self.path);
+        }
+
+        std::fs::rename(&tmp_path, &self.path)
             .map_err(|e| ConfigFilesError::IO(self.path.to_string_lossy().into(), e))?;
+
         Ok(())
     }
 
+    /// Appends `.{n}` to `path`'s file name, e.g. `playerlist.json.2`.
+    fn backup_path(path: &Path, n: usize) -> PathBuf {
+        Self::sibling_with_suffix(path, &n.to_string())
+    }
+
+    /// Appends `.{suffix}` to `path`'s file name.
+    fn sibling_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(".");
+        name.push(suffix);
+        PathBuf::from(name)
+    }
+
+    /// Shifts `path.1..MAX_BACKUPS` up by one slot, dropping the oldest, then
+    /// copies the current (pre-save) file into `path.1`. Best effort: a
+    /// failure here is only logged.
+    fn rotate_backups(path: &Path) {
+        for n in (1..MAX_BACKUPS).rev() {
+            let from = Self::backup_path(path, n);
+            let to = Self::backup_path(path, n + 1);
+            if from.exists() {
+                if let Err(e) = std::fs::rename(&from, &to) {
+                    tracing::warn!("Failed to rotate playerlist backup {:?}: {:?}", from, e);
+                }
+            }
+        }
+
+        let newest_backup = Self::backup_path(path, 1);
+        if let Err(e) = std::fs::copy(path, &newest_backup) {
+            tracing::warn!(
+                "Failed to create playerlist backup {:?}: {:?}",
+                newest_backup,
+                e
+            );
+        }
+    }
+
+    /// Re-save this playerlist under a different format, switching `path`'s
+    /// extension to match. A one-shot conversion: load the old file with
+    /// [`PlayerRecords::load_from`], then call this.
+    ///
+    /// # Errors
+    /// If the playerlist could not be saved in the new format.
+    pub fn convert_to(&mut self, format: PlayerListFormat) -> Result<(), ConfigFilesError> {
+        self.path.set_extension(format.extension());
+        self.save()
+    }
+
+    /// Load an external playerlist (e.g. shared by a teammate, or a
+    /// community "known cheaters" list) for merging in with
+    /// [`PlayerRecords::merge_from`]. Unlike [`PlayerRecords::load_from`],
+    /// the result isn't tied to `path` as a save location.
+    ///
+    /// # Errors
+    /// If the file could not be located, read, or parsed.
+    pub fn load_foreign(path: PathBuf) -> Result<Self, ConfigFilesError> {
+        Self::read_and_parse(&path)
+    }
+
+    /// Merge `other` (typically loaded with [`PlayerRecords::load_foreign`])
+    /// into this playerlist according to `policy`. A SteamID `other` has that
+    /// this playerlist doesn't is always inserted. For one both have,
+    /// [`MergePolicy::OnlyFillGaps`] leaves the local record untouched;
+    /// under the other policies `previous_names` are unioned, `created` keeps
+    /// the earlier timestamp, and `custom_data` is deep-merged key-by-key,
+    /// with only `verdict` (and `custom_data` key collisions) depending on
+    /// `policy`.
+    pub fn merge_from(&mut self, other: Self, policy: MergePolicy) -> MergeSummary {
+        let mut summary = MergeSummary::default();
+
+        for (steamid, incoming) in other.records {
+            match self.records.entry(steamid) {
+                std::collections::hash_map::Entry::Vacant(slot) => {
+                    slot.insert(incoming);
+                    summary.added.push(steamid);
+                }
+                std::collections::hash_map::Entry::Occupied(_)
+                    if policy == MergePolicy::OnlyFillGaps =>
+                {
+                    summary.skipped.push(steamid);
+                }
+                std::collections::hash_map::Entry::Occupied(mut slot) => {
+                    if Self::merge_record(slot.get_mut(), incoming, policy) {
+                        summary.updated.push(steamid);
+                    } else {
+                        summary.skipped.push(steamid);
+                    }
+                }
+            }
+        }
+
+        summary
+    }
+
+    /// Merge `incoming` into `local` in place per `policy`, returning whether
+    /// anything actually changed.
+    fn merge_record(local: &mut PlayerRecord, incoming: PlayerRecord, policy: MergePolicy) -> bool {
+        let mut changed = false;
+
+        for name in incoming.previous_names {
+            if !local.previous_names.contains(&name) {
+                local.previous_names.push(name);
+                changed = true;
+            }
+        }
+
+        if incoming.created < local.created {
+            local.created = incoming.created;
+            changed = true;
+        }
+
+        let incoming_is_newer = incoming.modified > local.modified;
+        let take_incoming_verdict = policy == MergePolicy::PreferNewest && incoming_is_newer;
+        if take_incoming_verdict && incoming.verdict != local.verdict {
+            local.verdict = incoming.verdict;
+            local.modified = incoming.modified;
+            changed = true;
+        }
+
+        if Self::merge_custom_data(
+            &mut local.custom_data,
+            incoming.custom_data,
+            take_incoming_verdict,
+        ) {
+            changed = true;
+        }
+
+        changed
+    }
+
+    /// Deep-merge `incoming` into `local`, key by key: local keys win unless
+    /// `overwrite_local`, in which case a colliding incoming value
+    /// overwrites. Missing keys are always filled in either way. Returns
+    /// whether anything changed. A no-op if either side isn't a JSON object.
+    fn merge_custom_data(
+        local: &mut serde_json::Value,
+        incoming: serde_json::Value,
+        overwrite_local: bool,
+    ) -> bool {
+        let (Some(incoming_obj), Some(local_obj)) = (incoming.as_object(), local.as_object_mut())
+        else {
+            return false;
+        };
+
+        let mut changed = false;
+        for (key, value) in incoming_obj {
+            match local_obj.get(key) {
+                None => {
+                    local_obj.insert(key.clone(), value.clone());
+                    changed = true;
+                }
+                Some(existing) if overwrite_local && existing != value => {
+                    local_obj.insert(key.clone(), value.clone());
+                    changed = true;
+                }
+                Some(_) => {}
+            }
+        }
+        changed
+    }
+
     /// Attempt to save the `PlayerRecords`, log errors and ignore result
     pub fn save_ok(&self) {
         if let Err(e) = self.save() {
@@ -128,6 +378,19 @@ impl PlayerRecords {
         Settings::locate_config_directory().map(|dir| dir.join("playerlist.json"))
     }
 
+    /// As [`PlayerRecords::locate_playerlist_file`], but for a playerlist
+    /// stored in `format` rather than assuming JSON.
+    ///
+    /// # Errors
+    /// If the config directory could not be located (usually because no valid
+    /// home directory was found)
+    pub fn locate_playerlist_file_with_format(
+        format: PlayerListFormat,
+    ) -> Result<PathBuf, ConfigFilesError> {
+        Settings::locate_config_directory()
+            .map(|dir| dir.join("playerlist").with_extension(format.extension()))
+    }
+
     pub fn update_name(&mut self, steamid: SteamID, name: Arc<str>) {
         if let Some(record) = self.records.get_mut(&steamid) {
             if !record.previous_names.contains(&name) {
@@ -160,6 +423,322 @@ impl DerefMut for PlayerRecords {
     fn deref_mut(&mut self) -> &mut Self::Target { &mut self.records }
 }
 
+/// The on-disk serialization format a playerlist file is stored in.
+///
+/// Format is picked per-file by extension (`.ron` vs everything else, which
+/// is treated as JSON) so a user can drop either kind of file in and have it
+/// load correctly, while [`Settings`] controls which one new playerlists are
+/// created as.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlayerListFormat {
+    #[default]
+    Json,
+    Ron,
+}
+
+impl PlayerListFormat {
+    /// Guess the format of a playerlist from its file extension, defaulting
+    /// to JSON for anything that isn't recognised as RON (including files
+    /// with no extension at all).
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(std::ffi::OsStr::to_str) {
+            Some("ron") => Self::Ron,
+            _ => Self::Json,
+        }
+    }
+
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Ron => "ron",
+        }
+    }
+}
+
+/// How to resolve conflicts when merging an external playerlist in via
+/// [`PlayerRecords::merge_from`]. In every variant, a SteamID the local
+/// playerlist doesn't already have is always imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// For a SteamID both sides have, take whichever record's `verdict` was
+    /// modified more recently, and let a newer `custom_data` key overwrite a
+    /// local one.
+    PreferNewest,
+    /// Never let an import change an existing local `verdict` or overwrite a
+    /// `custom_data` key, even if the import is newer. Non-conflicting
+    /// fields (`previous_names`, `created`) still merge.
+    PreferLocal,
+    /// The most conservative option: only add records for SteamIDs that
+    /// aren't tracked locally at all, and leave every existing record
+    /// completely untouched.
+    OnlyFillGaps,
+}
+
+/// What changed as a result of a [`PlayerRecords::merge_from`] call, so the
+/// UI can show the user what an import actually did before (or after) they
+/// commit to it.
+#[derive(Debug, Clone, Default)]
+pub struct MergeSummary {
+    pub added: Vec<SteamID>,
+    pub updated: Vec<SteamID>,
+    pub skipped: Vec<SteamID>,
+}
+
+/// Manages multiple named playerlist profiles (e.g. a competitive-scrim list
+/// vs. a casual-pub list) stored side-by-side in the config directory, with
+/// one active at a time tracked by name in [`Settings`]. The rest of the app
+/// keeps holding a single [`PlayerRecords`]; only the backing file changes
+/// underneath it when the active profile switches.
+pub struct PlayerListProfiles {
+    dir: PathBuf,
+    /// Format to create a *new* profile's file in. Consulted by
+    /// [`PlayerListProfiles::path_for`] only when no file already exists for
+    /// that profile under either supported extension.
+    default_format: PlayerListFormat,
+}
+
+impl PlayerListProfiles {
+    /// The profile name used for the plain `playerlist.json` that existed
+    /// before profiles did, so upgrading doesn't move anyone's file.
+    pub const DEFAULT_PROFILE: &'static str = "default";
+
+    /// # Errors
+    /// If the config directory could not be located (usually because no valid
+    /// home directory was found)
+    pub fn new(default_format: PlayerListFormat) -> Result<Self, ConfigFilesError> {
+        Ok(Self {
+            dir: Settings::locate_config_directory()?,
+            default_format,
+        })
+    }
+
+    /// As [`PlayerListProfiles::new`], taking `default_format` from
+    /// `settings` rather than the caller.
+    ///
+    /// # Errors
+    /// If the config directory could not be located (usually because no valid
+    /// home directory was found)
+    pub fn from_settings(settings: &Settings) -> Result<Self, ConfigFilesError> {
+        Self::new(settings.playerlist_format())
+    }
+
+    /// The path a named profile's playerlist is (or would be) stored at. If a
+    /// file already exists for this profile under either supported format's
+    /// extension, that exact path is returned — this is what makes
+    /// [`PlayerListProfiles::switch_to`] and
+    /// [`PlayerListProfiles::delete_profile`] keep working after a profile is
+    /// converted between formats with [`PlayerRecords::convert_to`].
+    /// Otherwise, a path ending in `default_format`'s extension is returned
+    /// for a profile that doesn't exist yet.
+    #[must_use]
+    pub fn path_for(&self, name: &str) -> PathBuf {
+        let base = if name == Self::DEFAULT_PROFILE {
+            self.dir.join("playerlist")
+        } else {
+            self.dir.join(format!("playerlist.{name}"))
+        };
+
+        [PlayerListFormat::Json, PlayerListFormat::Ron]
+            .into_iter()
+            .map(|format| base.with_extension(format.extension()))
+            .find(|candidate| candidate.exists())
+            .unwrap_or_else(|| base.with_extension(self.default_format.extension()))
+    }
+
+    /// List every profile discovered in the config directory, always
+    /// including [`PlayerListProfiles::DEFAULT_PROFILE`] even if its file
+    /// doesn't exist yet.
+    #[must_use]
+    pub fn list_profiles(&self) -> Vec<String> {
+        let mut profiles = vec![Self::DEFAULT_PROFILE.to_owned()];
+
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return profiles;
+        };
+
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let Some(name) = file_name.to_str().and_then(|file_name| {
+                let rest = file_name.strip_prefix("playerlist.")?;
+                rest.strip_suffix(".json").or_else(|| rest.strip_suffix(".ron"))
+            }) else {
+                continue;
+            };
+
+            if !profiles.iter().any(|p| p == name) {
+                profiles.push(name.to_owned());
+            }
+        }
+
+        profiles
+    }
+
+    /// Create a new, empty profile named `name` and save it immediately so it
+    /// shows up in [`PlayerListProfiles::list_profiles`].
+    ///
+    /// # Errors
+    /// If a file for this profile already exists, or it could not be written.
+    pub fn create_profile(&self, name: &str) -> Result<(), ConfigFilesError> {
+        let path = self.path_for(name);
+        if path.exists() {
+            return Err(ConfigFilesError::IO(
+                path.to_string_lossy().into(),
+                std::io::Error::new(ErrorKind::AlreadyExists, "profile already exists"),
+            ));
+        }
+
+        let mut playerlist = PlayerRecords::default();
+        playerlist.set_path(path);
+        playerlist.save()
+    }
+
+    /// Save `current` (the profile active before the switch), load and
+    /// return the playerlist for `name`, and record `name` as the active
+    /// profile in `settings` so the switch survives a restart.
+    ///
+    /// # Errors
+    /// If `current` failed to save, or `name`'s playerlist could not be
+    /// loaded.
+    pub fn switch_to(
+        &self,
+        current: &PlayerRecords,
+        name: &str,
+        settings: &mut Settings,
+    ) -> Result<PlayerRecords, ConfigFilesError> {
+        current.save()?;
+        let playerlist = PlayerRecords::load_from(self.path_for(name))?;
+        settings.set_active_playerlist_profile(name);
+        Ok(playerlist)
+    }
+
+    /// Delete a profile's playerlist file from disk. Callers are responsible
+    /// for switching away from a profile before deleting it.
+    ///
+    /// # Errors
+    /// If the file exists but could not be removed.
+    pub fn delete_profile(&self, name: &str) -> Result<(), ConfigFilesError> {
+        let path = self.path_for(name);
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(ConfigFilesError::IO(path.to_string_lossy().into(), e)),
+        }
+    }
+}
+
+/// Wraps a [`PlayerRecords`] with an in-memory cache that notices when its
+/// backing file has been changed out from under it — by a hand edit, an
+/// import via [`PlayerRecords::merge_from`] done by another process, or a
+/// synced file from another machine — and reloads it on the next
+/// [`CachedPlayerRecords::get`]. The check runs only in `get`:
+/// [`CachedPlayerRecords::save`] and [`CachedPlayerRecords::get_mut`] do not
+/// check, so an external edit made since the last `get` is overwritten by
+/// the next save.
+pub struct CachedPlayerRecords {
+    records: PlayerRecords,
+    /// mtime of `records.path` as of the last load or save *we* performed.
+    /// Compared against the file's current mtime to tell an external write
+    /// apart from one of our own.
+    known_mtime: Option<SystemTime>,
+    on_change: Option<Box<dyn Fn(&PlayerRecords) + Send + Sync>>,
+}
+
+impl CachedPlayerRecords {
+    #[must_use]
+    pub fn new(records: PlayerRecords) -> Self {
+        let known_mtime = Self::mtime_of(&records.path);
+        Self {
+            records,
+            known_mtime,
+            on_change: None,
+        }
+    }
+
+    /// Register a callback invoked whenever an external change is detected
+    /// and reloaded, so the UI can refresh whatever it's showing.
+    pub fn on_change(&mut self, callback: impl Fn(&PlayerRecords) + Send + Sync + 'static) {
+        self.on_change = Some(Box::new(callback));
+    }
+
+    /// The cached playerlist, transparently reloaded first if the backing
+    /// file's mtime is newer than what we last loaded or saved ourselves.
+    /// This is the normal way to read the playerlist; the mtime check is a
+    /// cheap stat, so it's fine to call this often.
+    pub fn get(&mut self) -> &PlayerRecords {
+        if self.externally_modified() {
+            if let Err(e) = self.reload() {
+                tracing::error!("Failed to reload externally-modified playerlist: {:?}", e);
+            }
+        }
+        &self.records
+    }
+
+    /// Unconditionally re-read the playerlist from disk via
+    /// [`PlayerRecords::load_from`] and refresh the cache, regardless of
+    /// what the mtime says. Use this for an explicit user-triggered refresh.
+    ///
+    /// # Errors
+    /// If the file could not be read or parsed.
+    pub fn get_raw(&mut self) -> Result<&PlayerRecords, ConfigFilesError> {
+        self.reload()?;
+        Ok(&self.records)
+    }
+
+    /// Mutable access to the cached copy, for callers about to change it
+    /// themselves (the UI editing a verdict, a merge being applied, etc).
+    pub fn get_mut(&mut self) -> &mut PlayerRecords {
+        &mut self.records
+    }
+
+    /// Save through to disk, then record the resulting mtime so our own
+    /// write isn't mistaken for an external change on the next [`Self::get`].
+    ///
+    /// # Errors
+    /// If the underlying save failed.
+    pub fn save(&mut self) -> Result<(), ConfigFilesError> {
+        self.records.save()?;
+        self.known_mtime = Self::mtime_of(&self.records.path);
+        Ok(())
+    }
+
+    /// Attempt to save, log errors and ignore the result.
+    pub fn save_ok(&mut self) {
+        if let Err(e) = self.save() {
+            tracing::error!("Failed to save playerlist: {:?}", e);
+        }
+    }
+
+    fn reload(&mut self) -> Result<(), ConfigFilesError> {
+        self.records = PlayerRecords::load_from(self.records.path.clone())?;
+        self.known_mtime = Self::mtime_of(&self.records.path);
+        if let Some(on_change) = &self.on_change {
+            on_change(&self.records);
+        }
+        Ok(())
+    }
+
+    fn externally_modified(&self) -> bool {
+        match (self.known_mtime, Self::mtime_of(&self.records.path)) {
+            (Some(known), Some(current)) => current > known,
+            // We had no mtime to compare against (e.g. the file didn't exist
+            // at load time) but one exists now: it was created out of band.
+            (None, Some(_)) => true,
+            _ => false,
+        }
+    }
+
+    fn mtime_of(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+}
+
+// Deliberately no `Deref` to `PlayerRecords` here: every read needs to go
+// through `get()`/`get_raw()` so the staleness check actually runs. A
+// `Deref` would let `.contains_key(..)`, `.iter()`, indexing, etc. reach the
+// cached copy directly and silently skip it.
+
 // PlayerRecord
 
 /// A Record of a player stored in the persistent personal playerlist
@@ -222,3 +801,320 @@ impl Display for Verdict {
 impl Default for Verdict {
     fn default() -> Self { Self::Player }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A scratch directory under the OS temp dir, unique to this test
+    /// process and call site, cleaned up when it's dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(unique: &str) -> Self {
+            let dir =
+                std::env::temp_dir().join(format!("player_records_test_{}_{unique}", std::process::id()));
+            std::fs::create_dir_all(&dir).expect("create temp dir");
+            Self(dir)
+        }
+
+        fn join(&self, name: &str) -> PathBuf { self.0.join(name) }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) { let _ = std::fs::remove_dir_all(&self.0); }
+    }
+
+    #[test]
+    fn save_is_atomic_and_leaves_no_tmp_file_behind() {
+        let dir = TempDir::new("atomic_save");
+        let path = dir.join("playerlist.json");
+
+        let mut records = PlayerRecords::default();
+        records.set_path(path.clone());
+        records
+            .records
+            .insert(SteamID::from(1), PlayerRecord::default());
+        records.save().expect("save should succeed");
+
+        assert!(path.exists());
+        assert!(!PlayerRecords::sibling_with_suffix(&path, "tmp").exists());
+    }
+
+    #[test]
+    fn save_rotates_previous_file_into_backup_ring() {
+        let dir = TempDir::new("rotation");
+        let path = dir.join("playerlist.json");
+
+        let mut records = PlayerRecords::default();
+        records.set_path(path.clone());
+        for n in 0..(MAX_BACKUPS + 1) {
+            records
+                .records
+                .insert(SteamID::from(n as u64), PlayerRecord::default());
+            records.save().expect("save should succeed");
+        }
+
+        for n in 1..=MAX_BACKUPS {
+            assert!(
+                PlayerRecords::backup_path(&path, n).exists(),
+                "backup slot {n} should exist after {} saves",
+                MAX_BACKUPS + 1
+            );
+        }
+    }
+
+    #[test]
+    fn load_from_recovers_from_backup_when_primary_is_corrupt() {
+        let dir = TempDir::new("recovery");
+        let path = dir.join("playerlist.json");
+
+        let mut records = PlayerRecords::default();
+        records.set_path(path.clone());
+        let id = SteamID::from(42);
+        records.records.insert(id, PlayerRecord::default());
+        records.save().expect("save should succeed");
+
+        // A second, good save rotates the above into `path.1`.
+        records
+            .records
+            .insert(SteamID::from(43), PlayerRecord::default());
+        records.save().expect("save should succeed");
+
+        // Now corrupt the primary file directly, simulating an interrupted
+        // write from an older version that didn't write atomically.
+        std::fs::write(&path, "{ this is not valid json").expect("corrupt primary file");
+
+        let recovered = PlayerRecords::load_from(path.clone()).expect("should recover from backup");
+        assert!(recovered.records.contains_key(&id));
+    }
+
+    #[test]
+    fn ron_playerlist_round_trips_through_save_and_load_from() {
+        let dir = TempDir::new("ron_round_trip");
+        let path = dir.join("playerlist.ron");
+        let id = SteamID::from(55);
+
+        let mut records = PlayerRecords::default();
+        records.set_path(path.clone());
+        records.records.insert(
+            id,
+            record_at(Verdict::Cheater, 0, serde_json::json!({"note": "ron test"})),
+        );
+        records.save().expect("ron save should succeed");
+
+        let contents = std::fs::read_to_string(&path).expect("read saved ron file");
+        assert!(
+            !contents.trim_start().starts_with('{'),
+            "a RON file shouldn't look like JSON: {contents}"
+        );
+
+        let loaded = PlayerRecords::load_from(path).expect("ron load should succeed");
+        assert_eq!(loaded.records[&id].verdict, Verdict::Cheater);
+        assert_eq!(loaded.records[&id].custom_data["note"], "ron test");
+    }
+
+    #[test]
+    fn path_for_finds_an_existing_profile_regardless_of_format() {
+        let dir = TempDir::new("profile_path_for");
+        let profiles = PlayerListProfiles {
+            dir: dir.0.clone(),
+            default_format: PlayerListFormat::Json,
+        };
+
+        // Nothing on disk yet: falls back to the configured default format.
+        assert_eq!(profiles.path_for("scrim"), dir.join("playerlist.scrim.json"));
+
+        // Once a profile has been converted to RON, `path_for` should find
+        // the `.ron` file rather than assuming the default format's `.json`.
+        std::fs::write(dir.join("playerlist.scrim.ron"), "()").expect("write ron profile");
+        assert_eq!(profiles.path_for("scrim"), dir.join("playerlist.scrim.ron"));
+    }
+
+    fn steamid(id: u64) -> SteamID { SteamID::from(id) }
+
+    fn record_at(
+        verdict: Verdict,
+        seconds_ago: i64,
+        custom_data: serde_json::Value,
+    ) -> PlayerRecord {
+        let timestamp = Utc::now() - chrono::Duration::seconds(seconds_ago);
+        PlayerRecord {
+            custom_data,
+            verdict,
+            previous_names: Vec::new(),
+            modified: timestamp,
+            created: timestamp,
+        }
+    }
+
+    fn records_with(id: SteamID, record: PlayerRecord) -> PlayerRecords {
+        let mut records = PlayerRecords::default();
+        records.records.insert(id, record);
+        records
+    }
+
+    #[test]
+    fn prefer_newest_overwrites_stale_local_verdict_and_custom_data() {
+        let id = steamid(1);
+        let mut local = records_with(
+            id,
+            record_at(
+                Verdict::Player,
+                3600,
+                serde_json::json!({"note": "trusted teammate"}),
+            ),
+        );
+        let other = records_with(
+            id,
+            record_at(
+                Verdict::Cheater,
+                0,
+                serde_json::json!({"note": "reported as cheater"}),
+            ),
+        );
+
+        let summary = local.merge_from(other, MergePolicy::PreferNewest);
+
+        assert_eq!(summary.updated, vec![id]);
+        assert_eq!(local.records[&id].verdict, Verdict::Cheater);
+        assert_eq!(
+            local.records[&id].custom_data["note"],
+            "reported as cheater"
+        );
+    }
+
+    #[test]
+    fn prefer_local_never_overwrites_verdict_or_custom_data() {
+        let id = steamid(2);
+        let mut local = records_with(
+            id,
+            record_at(
+                Verdict::Player,
+                3600,
+                serde_json::json!({"note": "trusted teammate"}),
+            ),
+        );
+        let other = records_with(
+            id,
+            record_at(
+                Verdict::Cheater,
+                0,
+                serde_json::json!({"note": "reported as cheater"}),
+            ),
+        );
+
+        let summary = local.merge_from(other, MergePolicy::PreferLocal);
+
+        assert_eq!(summary.skipped, vec![id]);
+        assert_eq!(local.records[&id].verdict, Verdict::Player);
+        assert_eq!(
+            local.records[&id].custom_data["note"],
+            "trusted teammate"
+        );
+    }
+
+    #[test]
+    fn only_fill_gaps_adds_new_but_never_touches_existing() {
+        let existing_id = steamid(3);
+        let new_id = steamid(4);
+        let mut local = records_with(
+            existing_id,
+            record_at(Verdict::Player, 0, serde_json::json!({})),
+        );
+        let mut other = PlayerRecords::default();
+        other
+            .records
+            .insert(existing_id, record_at(Verdict::Cheater, 0, serde_json::json!({})));
+        other
+            .records
+            .insert(new_id, record_at(Verdict::Suspicious, 0, serde_json::json!({})));
+
+        let summary = local.merge_from(other, MergePolicy::OnlyFillGaps);
+
+        assert_eq!(summary.added, vec![new_id]);
+        assert_eq!(summary.skipped, vec![existing_id]);
+        assert_eq!(local.records[&existing_id].verdict, Verdict::Player);
+        assert_eq!(local.records[&new_id].verdict, Verdict::Suspicious);
+    }
+
+    /// Force `path`'s mtime forward, simulating an external write that a
+    /// coarse filesystem clock might otherwise land in the same tick as our
+    /// own last save.
+    fn bump_mtime_forward(path: &Path) {
+        let file = std::fs::File::open(path).expect("open file to bump mtime");
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(5))
+            .expect("set mtime");
+    }
+
+    #[test]
+    fn get_reloads_after_external_modification() {
+        let dir = TempDir::new("cache_reload");
+        let path = dir.join("playerlist.json");
+        let external_id = steamid(100);
+
+        let mut records = PlayerRecords::default();
+        records.set_path(path.clone());
+        records.save().expect("initial save");
+
+        let mut cached = CachedPlayerRecords::new(records);
+        assert!(!cached.get().records.contains_key(&external_id));
+
+        let mut external = PlayerRecords::load_from(path.clone()).expect("reload externally");
+        external.records.insert(external_id, PlayerRecord::default());
+        external.save().expect("external save");
+        bump_mtime_forward(&path);
+
+        assert!(cached.get().records.contains_key(&external_id));
+    }
+
+    #[test]
+    fn save_does_not_trigger_a_spurious_reload() {
+        let dir = TempDir::new("cache_no_spurious_reload");
+        let path = dir.join("playerlist.json");
+        let id = steamid(101);
+
+        let mut records = PlayerRecords::default();
+        records.set_path(path);
+
+        let mut cached = CachedPlayerRecords::new(records);
+        let reload_count = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = std::sync::Arc::clone(&reload_count);
+        cached.on_change(move |_| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        });
+
+        cached.get_mut().records.insert(id, PlayerRecord::default());
+        cached.save().expect("save");
+
+        assert!(cached.get().records.contains_key(&id));
+        assert_eq!(reload_count.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn on_change_fires_when_an_external_edit_is_reloaded() {
+        let dir = TempDir::new("cache_on_change");
+        let path = dir.join("playerlist.json");
+        let external_id = steamid(102);
+
+        let mut records = PlayerRecords::default();
+        records.set_path(path.clone());
+        records.save().expect("initial save");
+
+        let mut cached = CachedPlayerRecords::new(records);
+        let saw_external_record = std::sync::Arc::new(std::sync::Mutex::new(false));
+        let seen = std::sync::Arc::clone(&saw_external_record);
+        cached.on_change(move |records| {
+            *seen.lock().unwrap() = records.records.contains_key(&external_id);
+        });
+
+        let mut external = PlayerRecords::load_from(path.clone()).expect("reload externally");
+        external.records.insert(external_id, PlayerRecord::default());
+        external.save().expect("external save");
+        bump_mtime_forward(&path);
+
+        cached.get();
+
+        assert!(*saw_external_record.lock().unwrap());
+    }
+}
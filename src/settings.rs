@@ -0,0 +1,73 @@
+use std::{io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::player_records::{PlayerListFormat, PlayerListProfiles};
+
+/// Errors that can occur while reading or writing one of the small
+/// configuration files this crate persists to the user's config directory
+/// (settings, playerlist, etc).
+#[derive(Debug, Error)]
+pub enum ConfigFilesError {
+    #[error("IO error at {0}: {1}")]
+    IO(String, io::Error),
+    #[error("Failed to parse {0}: {1}")]
+    Json(String, serde_json::Error),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// Persistent user preferences for this client, stored in the config
+/// directory alongside the playerlist.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Format new playerlists (and new playerlist profiles) are created in.
+    /// Existing playerlists keep loading in whatever format they were
+    /// already saved as, regardless of this setting.
+    pub playerlist_format: PlayerListFormat,
+    /// Name of the playerlist profile currently active. Consulted by
+    /// [`crate::player_records::PlayerRecords::load_or_create_from_settings`] when no
+    /// `--playerlist` override is given on the command line, and updated by
+    /// [`crate::player_records::PlayerListProfiles::switch_to`] whenever the
+    /// user switches profiles.
+    pub active_playerlist_profile: String,
+}
+
+impl Settings {
+    #[must_use]
+    pub fn playerlist_format(&self) -> PlayerListFormat { self.playerlist_format }
+
+    pub fn set_playerlist_format(&mut self, format: PlayerListFormat) {
+        self.playerlist_format = format;
+    }
+
+    #[must_use]
+    pub fn active_playerlist_profile(&self) -> &str { &self.active_playerlist_profile }
+
+    pub fn set_active_playerlist_profile(&mut self, name: impl Into<String>) {
+        self.active_playerlist_profile = name.into();
+    }
+
+    /// # Errors
+    /// If the config directory could not be located (usually because no
+    /// valid home directory was found)
+    pub fn locate_config_directory() -> Result<PathBuf, ConfigFilesError> {
+        let dir = dirs::config_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not locate a config directory"))?
+            .join("tf2-bot-detector");
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| ConfigFilesError::IO(dir.to_string_lossy().into(), e))?;
+        Ok(dir)
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            playerlist_format: PlayerListFormat::default(),
+            active_playerlist_profile: PlayerListProfiles::DEFAULT_PROFILE.to_owned(),
+        }
+    }
+}